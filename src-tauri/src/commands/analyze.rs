@@ -3,10 +3,10 @@ use crate::python;
 
 #[tauri::command]
 pub async fn analyze_url(app: AppHandle, url: String) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "analyze", &["video", &url], None).await
+    python::run_python_module(&app, "analyze", &["video", &url], None, None, None).await
 }
 
 #[tauri::command]
 pub async fn analyze_playlist(app: AppHandle, url: String) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "analyze", &["playlist", &url], None).await
+    python::run_python_module(&app, "analyze", &["playlist", &url], None, None, None).await
 }
@@ -1,20 +1,57 @@
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, State};
 use tokio::sync::Mutex;
-use crate::python::{self, ProcessHandle, new_process_handle, kill_process, LogPayload};
+use crate::python::{self, new_cancel_flag, new_process_handle, stop_process, CancelFlag, JobId, LogPayload, ProcessHandle};
 
-/// Shared state for the current download process
+/// Default grace period for a cooperative stop before escalating to SIGKILL.
+const DEFAULT_CANCEL_GRACE: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Lifecycle of a single queued/running download job.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum JobStatus {
+    Queued,
+    Running,
+    Completed,
+    Cancelled,
+    Failed,
+}
+
+struct JobEntry {
+    request: DownloadRequest,
+    process: ProcessHandle,
+    cancel_requested: CancelFlag,
+    status: JobStatus,
+    error: Option<String>,
+}
+
+/// Snapshot of a job suitable for sending to the frontend.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobSummary {
+    pub job_id: JobId,
+    pub url: String,
+    pub status: JobStatus,
+    pub error: Option<String>,
+}
+
+/// Shared state for the download job queue. Replaces the old single
+/// in-flight `ProcessHandle`/`is_downloading` flag with a registry so
+/// several jobs can be queued and run concurrently.
 pub struct DownloadState {
-    pub process: ProcessHandle,
-    pub is_downloading: Arc<Mutex<bool>>,
+    next_id: AtomicU64,
+    jobs: Arc<Mutex<HashMap<JobId, JobEntry>>>,
+    max_concurrent: Arc<Mutex<usize>>,
 }
 
 impl Default for DownloadState {
     fn default() -> Self {
         Self {
-            process: new_process_handle(),
-            is_downloading: Arc::new(Mutex::new(false)),
+            next_id: AtomicU64::new(1),
+            jobs: Arc::new(Mutex::new(HashMap::new())),
+            max_concurrent: Arc::new(Mutex::new(2)),
         }
     }
 }
@@ -26,7 +63,7 @@ pub struct ChapterRequest {
     pub end_time: f64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DownloadRequest {
     pub url: String,
     pub quality: String,
@@ -41,34 +78,35 @@ pub struct DownloadRequest {
     pub custom_bitrate: Option<u32>,
     pub per_resolution: Option<std::collections::HashMap<String, u32>>,
     pub chapters: Option<Vec<ChapterRequest>>,
+    /// Raw extra yt-dlp arguments (e.g. `--extractor-args`, `--format-sort`,
+    /// rate limits), forwarded verbatim after the generated flags.
+    pub extra_args: Option<Vec<String>>,
 }
 
-#[tauri::command]
-pub async fn start_download(
-    app: AppHandle,
-    state: State<'_, DownloadState>,
-    request: DownloadRequest,
-) -> Result<serde_json::Value, String> {
-    // Check if already downloading
-    {
-        let mut downloading = state.is_downloading.lock().await;
-        if *downloading {
-            return Err("A download is already in progress".to_string());
-        }
-        *downloading = true;
+/// Read `max_concurrent_downloads` from user settings (via the Python
+/// settings module), defaulting to 2 and clamping to at least 1.
+async fn refresh_max_concurrent(app: &AppHandle, max_concurrent: &Arc<Mutex<usize>>) {
+    let settings = python::load_settings_cached(app).await;
+    if let Some(n) = settings.get("max_concurrent_downloads").and_then(|v| v.as_u64()) {
+        *max_concurrent.lock().await = (n as usize).max(1);
     }
+}
 
-    let _ = app.emit("download-log", LogPayload {
-        level: "info".to_string(),
-        message: "Starting download pipeline...".to_string(),
-    });
+/// Read `cancel_grace_seconds` from user settings, defaulting to 5s.
+async fn cancel_grace(app: &AppHandle) -> std::time::Duration {
+    python::load_settings_cached(app).await
+        .get("cancel_grace_seconds")
+        .and_then(|v| v.as_u64())
+        .map(std::time::Duration::from_secs)
+        .unwrap_or(DEFAULT_CANCEL_GRACE)
+}
 
-    // Build args for the Python download module
+fn build_args(app: &AppHandle, request: &DownloadRequest) -> Vec<String> {
     let mut args: Vec<String> = vec![
         "run".to_string(),
-        "--url".to_string(), request.url,
-        "--quality".to_string(), request.quality,
-        "--output-dir".to_string(), request.output_dir,
+        "--url".to_string(), request.url.clone(),
+        "--quality".to_string(), request.quality.clone(),
+        "--output-dir".to_string(), request.output_dir.clone(),
     ];
 
     let has_chapters = request.chapters.as_ref().map_or(false, |c| !c.is_empty());
@@ -118,47 +156,251 @@ pub async fn start_download(
         }
     }
 
+    // Prefer the binary managed by the Rust-side updater over whatever
+    // yt-dlp pip happens to bring in, so all subsystems agree on one version.
+    if let Ok(path) = crate::ytdlp::binary_path(app) {
+        if path.exists() {
+            args.push("--ytdlp-path".to_string());
+            args.push(path.to_string_lossy().to_string());
+        }
+    }
+
+    if let Some(ref extra) = request.extra_args {
+        args.extend(extra.iter().cloned());
+    }
+
+    args
+}
+
+/// Run a single job's Python download module to completion, then hand the
+/// freed concurrency slot back to the scheduler.
+async fn run_job(
+    app: AppHandle,
+    jobs: Arc<Mutex<HashMap<JobId, JobEntry>>>,
+    max_concurrent: Arc<Mutex<usize>>,
+    job_id: JobId,
+) {
+    let (args, process_handle, cancel_requested, url) = {
+        let mut guard = jobs.lock().await;
+        let entry = match guard.get_mut(&job_id) {
+            Some(entry) => entry,
+            None => return,
+        };
+        (
+            build_args(&app, &entry.request),
+            entry.process.clone(),
+            entry.cancel_requested.clone(),
+            entry.request.url.clone(),
+        )
+    };
+
     let arg_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    let process_handle = state.process.clone();
+    let started_at = std::time::Instant::now();
 
     let result = python::run_python_module(
         &app,
         "download",
         &arg_refs,
         Some(process_handle),
+        Some(job_id),
+        Some(cancel_requested),
     ).await;
 
-    // Reset downloading state
-    {
-        let mut downloading = state.is_downloading.lock().await;
-        *downloading = false;
-    }
+    let duration_seconds = started_at.elapsed().as_secs_f64();
 
-    // Emit completion event
-    match &result {
-        Ok(data) => {
-            let _ = app.emit("download-complete", data.clone());
+    // A cancel can race the job's own spawn/run, so never let a late
+    // `Ok`/`Err` here clobber a status that cancellation already set.
+    let was_cancelled = {
+        let mut guard = jobs.lock().await;
+        match guard.get_mut(&job_id) {
+            Some(entry) if entry.status == JobStatus::Cancelled => true,
+            Some(entry) => {
+                match &result {
+                    Ok(_) => entry.status = JobStatus::Completed,
+                    Err(e) => {
+                        entry.status = JobStatus::Failed;
+                        entry.error = Some(e.clone());
+                    }
+                }
+                false
+            }
+            None => true,
         }
-        Err(e) => {
-            let _ = app.emit("download-error", e.clone());
+    };
+
+    if !was_cancelled {
+        match &result {
+            Ok(data) => {
+                let mut payload = data.clone();
+                if let Some(obj) = payload.as_object_mut() {
+                    obj.insert("job_id".to_string(), serde_json::json!(job_id));
+                }
+                let _ = app.emit("download-complete", payload);
+
+                let output_path = data.get("output_path").and_then(|v| v.as_str()).map(String::from);
+                // Webhooks are a notification side-effect, not part of the
+                // job's own lifecycle - dispatch them on their own task so a
+                // slow/unreachable endpoint can't delay schedule_next from
+                // handing this slot to the next queued job.
+                let notify_app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    crate::notifier::notify_completion(&notify_app, crate::notifier::WebhookPayload {
+                        title: "Download complete".to_string(),
+                        url,
+                        output_path,
+                        status: "complete".to_string(),
+                        duration_seconds: Some(duration_seconds),
+                    }, true).await;
+                });
+            }
+            Err(e) => {
+                let _ = app.emit("download-error", serde_json::json!({
+                    "job_id": job_id,
+                    "message": e,
+                }));
+
+                let notify_app = app.clone();
+                tauri::async_runtime::spawn(async move {
+                    crate::notifier::notify_completion(&notify_app, crate::notifier::WebhookPayload {
+                        title: "Download failed".to_string(),
+                        url,
+                        output_path: None,
+                        status: "error".to_string(),
+                        duration_seconds: Some(duration_seconds),
+                    }, false).await;
+                });
+            }
         }
     }
 
-    result
+    schedule_next(app, jobs, max_concurrent).await;
+}
+
+/// Start as many queued jobs as the concurrency budget allows.
+async fn schedule_next(
+    app: AppHandle,
+    jobs: Arc<Mutex<HashMap<JobId, JobEntry>>>,
+    max_concurrent: Arc<Mutex<usize>>,
+) {
+    let limit = *max_concurrent.lock().await;
+
+    loop {
+        // Select the next queued job and flip it to Running in the same
+        // locked section as the running-count check, so two overlapping
+        // calls to schedule_next (e.g. one job finishing while another is
+        // being queued) can't both see room under `limit` and spawn the
+        // same job, or spawn more jobs than the budget allows.
+        let next_id = {
+            let mut guard = jobs.lock().await;
+            let running = guard.values().filter(|e| e.status == JobStatus::Running).count();
+            if running >= limit {
+                None
+            } else {
+                let id = guard.iter()
+                    .filter(|(_, e)| e.status == JobStatus::Queued)
+                    .map(|(id, _)| *id)
+                    .min();
+                if let Some(id) = id {
+                    if let Some(entry) = guard.get_mut(&id) {
+                        entry.status = JobStatus::Running;
+                    }
+                }
+                id
+            }
+        };
+
+        let Some(job_id) = next_id else { break };
+
+        tauri::async_runtime::spawn(run_job(
+            app.clone(),
+            jobs.clone(),
+            max_concurrent.clone(),
+            job_id,
+        ));
+    }
+}
+
+#[tauri::command]
+pub async fn start_download(
+    app: AppHandle,
+    state: State<'_, DownloadState>,
+    request: DownloadRequest,
+) -> Result<JobId, String> {
+    refresh_max_concurrent(&app, &state.max_concurrent).await;
+
+    let job_id = state.next_id.fetch_add(1, Ordering::SeqCst);
+    let entry = JobEntry {
+        request,
+        process: new_process_handle(),
+        cancel_requested: new_cancel_flag(),
+        status: JobStatus::Queued,
+        error: None,
+    };
+    state.jobs.lock().await.insert(job_id, entry);
+
+    let _ = app.emit("download-log", LogPayload {
+        job_id: Some(job_id),
+        level: "info".to_string(),
+        message: "Download queued".to_string(),
+    });
+
+    schedule_next(app, state.jobs.clone(), state.max_concurrent.clone()).await;
+
+    Ok(job_id)
 }
 
 #[tauri::command]
 pub async fn cancel_download(
     app: AppHandle,
     state: State<'_, DownloadState>,
+    job_id: JobId,
+    force: Option<bool>,
 ) -> Result<(), String> {
-    kill_process(&state.process).await;
-    let mut downloading = state.is_downloading.lock().await;
-    *downloading = false;
+    let process = {
+        let mut guard = state.jobs.lock().await;
+        let entry = guard.get_mut(&job_id).ok_or("Unknown job id")?;
+        if entry.status != JobStatus::Running && entry.status != JobStatus::Queued {
+            return Ok(());
+        }
+        entry.status = JobStatus::Cancelled;
+        // Covers the job-not-yet-spawned window: stop_process() below is a
+        // no-op if the process hasn't been registered yet, so the flag is
+        // what actually kills it once run_child_blocking gets a child.
+        entry.cancel_requested.store(true, std::sync::atomic::Ordering::SeqCst);
+        entry.process.clone()
+    };
+
+    let force = force.unwrap_or(false);
+    let grace = cancel_grace(&app).await;
+    stop_process(&process, grace, force).await;
+
     let _ = app.emit("download-log", LogPayload {
+        job_id: Some(job_id),
         level: "warning".to_string(),
         message: "Download cancelled by user".to_string(),
     });
-    let _ = app.emit("download-cancelled", ());
+    let _ = app.emit("download-cancelled", job_id);
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn list_jobs(state: State<'_, DownloadState>) -> Result<Vec<JobSummary>, String> {
+    let guard = state.jobs.lock().await;
+    let mut jobs: Vec<JobSummary> = guard.iter()
+        .map(|(id, entry)| JobSummary {
+            job_id: *id,
+            url: entry.request.url.clone(),
+            status: entry.status,
+            error: entry.error.clone(),
+        })
+        .collect();
+    jobs.sort_by_key(|j| j.job_id);
+    Ok(jobs)
+}
+
+#[tauri::command]
+pub async fn clear_finished(state: State<'_, DownloadState>) -> Result<(), String> {
+    let mut guard = state.jobs.lock().await;
+    guard.retain(|_, entry| matches!(entry.status, JobStatus::Queued | JobStatus::Running));
     Ok(())
 }
@@ -1,18 +1,22 @@
-use tauri::AppHandle;
+use tauri::{AppHandle, Manager};
 use crate::python;
 
 #[tauri::command]
 pub async fn load_settings(app: AppHandle) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "settings", &["load"], None).await
+    let settings = python::run_python_module(&app, "settings", &["load"], None, None, None).await?;
+    *app.state::<python::SettingsCache>().lock().await = Some(settings.clone());
+    Ok(settings)
 }
 
 #[tauri::command]
 pub async fn save_settings(app: AppHandle, settings: serde_json::Value) -> Result<serde_json::Value, String> {
     let settings_str = serde_json::to_string(&settings).map_err(|e| e.to_string())?;
-    python::run_python_module(&app, "settings", &["save", &settings_str], None).await
+    let saved = python::run_python_module(&app, "settings", &["save", &settings_str], None, None, None).await?;
+    *app.state::<python::SettingsCache>().lock().await = Some(saved.clone());
+    Ok(saved)
 }
 
 #[tauri::command]
 pub async fn get_output_dir(app: AppHandle) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "settings", &["get-output-dir"], None).await
+    python::run_python_module(&app, "settings", &["get-output-dir"], None, None, None).await
 }
@@ -4,27 +4,91 @@ use crate::python;
 
 #[tauri::command]
 pub async fn get_system_info(app: AppHandle) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "sysmon", &["snapshot"], None).await
+    python::run_python_module(&app, "sysmon", &["snapshot"], None, None, None).await
 }
 
 #[tauri::command]
 pub async fn send_notification(app: AppHandle, title: String, message: String) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "notify", &[&title, &message], None).await
+    python::run_python_module(&app, "notify", &[&title, &message], None, None, None).await
+}
+
+/// Percent-encode a filesystem path into a `file://` URI. Beyond being the
+/// correct way to build a URI, this escapes commas (and anything else
+/// outside the unreserved set) that would otherwise confuse consumers
+/// expecting a bare URI string - notably dbus-send's `array:string:`
+/// shorthand below, which splits its argument on literal commas.
+fn file_uri(path: &str) -> String {
+    let mut uri = String::from("file://");
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                uri.push(byte as char);
+            }
+            _ => uri.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    uri
 }
 
 #[tauri::command]
 pub async fn open_file_location(path: String) -> Result<(), String> {
-    std::process::Command::new("open")
-        .arg("-R")
-        .arg(&path)
-        .spawn()
-        .map_err(|e| format!("Failed to open file location: {}", e))?;
+    #[cfg(target_os = "windows")]
+    {
+        std::process::Command::new("explorer")
+            .arg(format!("/select,{}", path))
+            .spawn()
+            .map_err(|e| format!("Failed to open file location: {}", e))?;
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        std::process::Command::new("open")
+            .arg("-R")
+            .arg(&path)
+            .spawn()
+            .map_err(|e| format!("Failed to open file location: {}", e))?;
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        // Ask the file manager to highlight the file via the FreeDesktop
+        // FileManager1 D-Bus interface; fall back to xdg-open on the
+        // containing folder if no file manager answers it.
+        let status = std::process::Command::new("dbus-send")
+            .args([
+                "--session",
+                "--print-reply",
+                "--dest=org.freedesktop.FileManager1",
+                "--type=method_call",
+                "/org/freedesktop/FileManager1",
+                "org.freedesktop.FileManager1.ShowItems",
+                &format!("array:string:{}", file_uri(&path)),
+                "string:",
+            ])
+            .status();
+
+        if status.map(|s| !s.success()).unwrap_or(true) {
+            let folder = std::path::Path::new(&path).parent().unwrap_or(std::path::Path::new("/"));
+            std::process::Command::new("xdg-open")
+                .arg(folder)
+                .spawn()
+                .map_err(|e| format!("Failed to open file location: {}", e))?;
+        }
+    }
+
     Ok(())
 }
 
 #[tauri::command]
 pub async fn open_folder(path: String) -> Result<(), String> {
-    std::process::Command::new("open")
+    #[cfg(target_os = "windows")]
+    let program = "explorer";
+    #[cfg(target_os = "macos")]
+    let program = "open";
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let program = "xdg-open";
+
+    std::process::Command::new(program)
         .arg(&path)
         .spawn()
         .map_err(|e| format!("Failed to open folder: {}", e))?;
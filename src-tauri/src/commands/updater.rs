@@ -1,13 +1,12 @@
 use tauri::AppHandle;
-use crate::python;
+use crate::ytdlp;
 
 #[tauri::command]
-pub async fn check_ytdlp_update(app: AppHandle) -> Result<serde_json::Value, String> {
-    python::run_python_module(&app, "updater", &["check"], None).await
+pub async fn check_ytdlp_update(app: AppHandle, nightly: bool) -> Result<serde_json::Value, String> {
+    ytdlp::check_for_update(&app, nightly).await
 }
 
 #[tauri::command]
 pub async fn install_ytdlp_update(app: AppHandle, version: String, nightly: bool) -> Result<serde_json::Value, String> {
-    let nightly_str = if nightly { "true" } else { "false" };
-    python::run_python_module(&app, "updater", &["install", &version, nightly_str], None).await
+    ytdlp::install_update(&app, &version, nightly).await
 }
@@ -1,5 +1,7 @@
 mod commands;
+mod notifier;
 mod python;
+mod ytdlp;
 
 use commands::download::DownloadState;
 
@@ -9,6 +11,7 @@ pub fn run() {
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .manage(DownloadState::default())
+        .manage(python::new_settings_cache())
         .invoke_handler(tauri::generate_handler![
             // Analyze
             commands::analyze::analyze_url,
@@ -16,6 +19,8 @@ pub fn run() {
             // Download
             commands::download::start_download,
             commands::download::cancel_download,
+            commands::download::list_jobs,
+            commands::download::clear_finished,
             // Settings
             commands::settings::load_settings,
             commands::settings::save_settings,
@@ -0,0 +1,77 @@
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use crate::python;
+
+/// Structured message posted to a user-configured webhook when a job
+/// finishes, mirroring the notifier pattern used by archival download
+/// tools (Discord/Slack/ntfy, etc).
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookPayload {
+    pub title: String,
+    pub url: String,
+    pub output_path: Option<String>,
+    pub status: String,
+    pub duration_seconds: Option<f64>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct WebhookConfig {
+    endpoint: String,
+    #[serde(default = "default_true")]
+    notify_on_success: bool,
+    #[serde(default = "default_true")]
+    notify_on_error: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+async fn configured_webhooks(app: &AppHandle) -> Vec<WebhookConfig> {
+    let settings = python::load_settings_cached(app).await;
+
+    settings.get("webhooks")
+        .and_then(|v| serde_json::from_value::<Vec<WebhookConfig>>(v.clone()).ok())
+        .unwrap_or_default()
+}
+
+/// How long to wait on a single webhook before giving up on it. A stuck
+/// endpoint shouldn't be able to hang the notifier indefinitely.
+const WEBHOOK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Fire the configured webhooks for a finished job. Failures are logged as
+/// debug events and never propagate, since a broken webhook shouldn't fail
+/// the download it's reporting on.
+pub async fn notify_completion(app: &AppHandle, payload: WebhookPayload, succeeded: bool) {
+    let webhooks = configured_webhooks(app).await;
+    if webhooks.is_empty() {
+        return;
+    }
+
+    let client = match reqwest::Client::builder().timeout(WEBHOOK_TIMEOUT).build() {
+        Ok(client) => client,
+        Err(e) => {
+            let _ = app.emit("download-log", python::LogPayload {
+                job_id: None,
+                level: "debug".to_string(),
+                message: format!("Failed to build webhook client: {}", e),
+            });
+            return;
+        }
+    };
+
+    for webhook in webhooks {
+        let should_fire = if succeeded { webhook.notify_on_success } else { webhook.notify_on_error };
+        if !should_fire {
+            continue;
+        }
+
+        if let Err(e) = client.post(&webhook.endpoint).json(&payload).send().await {
+            let _ = app.emit("download-log", python::LogPayload {
+                job_id: None,
+                level: "debug".to_string(),
+                message: format!("Webhook to {} failed: {}", webhook.endpoint, e),
+            });
+        }
+    }
+}
@@ -1,11 +1,26 @@
 use serde::{Deserialize, Serialize};
 use std::io::{BufRead, BufReader, Read};
 use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use tauri::{AppHandle, Emitter, Manager};
 use tokio::sync::Mutex;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Identifies a single download job in the queue (see `commands::download`).
+pub type JobId = u64;
+
+/// Shared per-job flag set by `cancel_download` before the underlying
+/// process is necessarily spawned/registered yet. Checked right after
+/// `cmd.spawn()` and again when the child is registered, so a cancel
+/// requested in that window still kills the process instead of letting it
+/// run to completion.
+pub type CancelFlag = Arc<AtomicBool>;
+
+pub fn new_cancel_flag() -> CancelFlag {
+    Arc::new(AtomicBool::new(false))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PythonEvent {
     pub event: String,
     #[serde(default)]
@@ -30,6 +45,8 @@ pub struct PythonEvent {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressPayload {
+    #[serde(default)]
+    pub job_id: Option<JobId>,
     pub stage: String,
     pub percent: f64,
     pub speed_mbps: f64,
@@ -39,6 +56,8 @@ pub struct ProgressPayload {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogPayload {
+    #[serde(default)]
+    pub job_id: Option<JobId>,
     pub level: String,
     pub message: String,
 }
@@ -88,18 +107,63 @@ pub fn get_python_dir(app: &AppHandle) -> String {
     "python".to_string()
 }
 
-/// Resolve which python3 to use
+/// Resolve which Python interpreter to use, probing common install
+/// locations per platform before falling back to `which`/`where`
+/// resolution against PATH.
 fn find_python() -> String {
-    for path in &[
+    #[cfg(target_os = "windows")]
+    let candidates: &[&str] = &[];
+
+    #[cfg(target_os = "macos")]
+    let candidates: &[&str] = &[
         "/opt/homebrew/bin/python3",
         "/usr/local/bin/python3",
         "/usr/bin/python3",
-    ] {
+    ];
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let candidates: &[&str] = &[
+        "/usr/bin/python3",
+        "/usr/local/bin/python3",
+        "/snap/bin/python3",
+    ];
+
+    for path in candidates {
         if std::path::Path::new(path).exists() {
             return path.to_string();
         }
     }
-    "python3".to_string()
+
+    #[cfg(target_os = "windows")]
+    {
+        // `py` is the recommended Windows launcher; prefer it over a bare
+        // `python.exe`, which may resolve to the Microsoft Store stub.
+        if which_on_path("py").is_some() {
+            return "py".to_string();
+        }
+        if let Some(path) = which_on_path("python.exe").or_else(|| which_on_path("python")) {
+            return path;
+        }
+        return "python".to_string();
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+        if let Some(path) = which_on_path("python3").or_else(|| which_on_path("python")) {
+            return path;
+        }
+        "python3".to_string()
+    }
+}
+
+/// Resolve a command name against PATH using the platform's lookup tool.
+fn which_on_path(name: &str) -> Option<String> {
+    let finder = if cfg!(target_os = "windows") { "where" } else { "which" };
+    let output = Command::new(finder).arg(name).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()?.lines().next().map(|s| s.trim().to_string())
 }
 
 /// Shared handle to a running Python child process for cancellation
@@ -109,6 +173,66 @@ pub fn new_process_handle() -> ProcessHandle {
     Arc::new(Mutex::new(None))
 }
 
+/// Last settings successfully loaded/saved via the `settings` module,
+/// managed as Tauri state. Lets callers that just need a couple of
+/// fields (like runtime overrides, below) avoid spawning a Python
+/// subprocess on every single `run_python_module` call.
+pub type SettingsCache = Arc<Mutex<Option<serde_json::Value>>>;
+
+pub fn new_settings_cache() -> SettingsCache {
+    Arc::new(Mutex::new(None))
+}
+
+/// Load settings, preferring the cache populated by `commands::settings`
+/// and falling back to a real `settings load` subprocess call (caching the
+/// result) if nothing has populated it yet. Returns an empty object rather
+/// than an error so callers can treat missing settings as "all defaults".
+pub async fn load_settings_cached(app: &AppHandle) -> serde_json::Value {
+    let cache = app.state::<SettingsCache>();
+
+    if let Some(settings) = cache.lock().await.clone() {
+        return settings;
+    }
+
+    match run_python_module(app, "settings", &["load"], None, None, None).await {
+        Ok(settings) => {
+            *cache.lock().await = Some(settings.clone());
+            settings
+        }
+        Err(_) => serde_json::Value::Object(Default::default()),
+    }
+}
+
+/// User-configurable overrides for how Python modules get invoked, so power
+/// users aren't stuck with the hard-coded interpreter/working directory.
+#[derive(Debug, Clone, Default)]
+struct RuntimeOverrides {
+    python_path: Option<String>,
+    working_dir: Option<String>,
+}
+
+/// Load runtime overrides from whatever settings are already cached.
+///
+/// This deliberately does NOT fall back to spawning a `settings load`
+/// subprocess: that subprocess is itself launched via `find_python()`/the
+/// override this function would produce, so doing so here would mean the
+/// override can never take effect until a *different* code path (the
+/// `settings` module is exempt from overrides, see `run_python_module`)
+/// has already populated the cache - e.g. the frontend calling
+/// `load_settings` on startup. Until then, overrides are simply not
+/// applied yet, which is a safe default.
+async fn load_overrides(app: &AppHandle) -> RuntimeOverrides {
+    let cache = app.state::<SettingsCache>();
+    let guard = cache.lock().await;
+    match guard.as_ref() {
+        Some(settings) => RuntimeOverrides {
+            python_path: settings.get("python_path").and_then(|v| v.as_str()).map(String::from),
+            working_dir: settings.get("ytdlp_working_dir").and_then(|v| v.as_str()).map(String::from),
+        },
+        None => RuntimeOverrides::default(),
+    }
+}
+
 /// Spawn a Python module and stream JSON line events back.
 /// Returns the final `result` data or an error.
 pub async fn run_python_module(
@@ -116,8 +240,15 @@ pub async fn run_python_module(
     module: &str,
     args: &[&str],
     process_handle: Option<ProcessHandle>,
+    job_id: Option<JobId>,
+    cancel_requested: Option<CancelFlag>,
 ) -> Result<serde_json::Value, String> {
-    let python = find_python();
+    let overrides = if module == "settings" {
+        RuntimeOverrides::default()
+    } else {
+        load_overrides(app).await
+    };
+
     let python_dir = get_python_dir(app);
 
     // PYTHONPATH must point to the parent of the python/ package directory
@@ -126,23 +257,126 @@ pub async fn run_python_module(
         .map(|p| p.to_string_lossy().to_string())
         .unwrap_or_else(|| ".".to_string());
 
-    // Log what we're doing for debugging
-    let _ = app.emit("download-log", LogPayload {
-        level: "debug".to_string(),
-        message: format!("Python: {} -m python.{} | PYTHONPATH={}", python, module, python_parent),
+    // Resolving the default interpreter (find_python) shells out to
+    // `which`/`where` when nothing is overridden, and spawning/reading the
+    // child is blocking too - all of it runs on a dedicated blocking thread
+    // so neither pins a tokio worker for a multi-minute download. Parsed
+    // events stream back over an mpsc channel that this async fn drains and
+    // re-emits.
+    let module_owned = module.to_string();
+    let args_owned: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+    let python_parent_for_cmd = python_parent.clone();
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel::<PythonEvent>();
+    let blocking_handle = process_handle.clone();
+    let outcome_task = tauri::async_runtime::spawn_blocking(move || {
+        let python = overrides.python_path.unwrap_or_else(find_python);
+
+        let _ = tx.send(PythonEvent {
+            event: "log".to_string(),
+            level: Some("debug".to_string()),
+            message: Some(format!("Python: {} -m python.{} | PYTHONPATH={}", python, module_owned, python_parent_for_cmd)),
+            ..Default::default()
+        });
+
+        let mut cmd = Command::new(&python);
+        cmd.arg("-m")
+            .arg(format!("python.{}", module_owned))
+            .args(&args_owned)
+            .env("PYTHONPATH", &python_parent_for_cmd)
+            .env("PYTHONIOENCODING", "utf-8")
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        if let Some(ref working_dir) = overrides.working_dir {
+            cmd.current_dir(working_dir);
+        }
+
+        run_child_blocking(cmd, blocking_handle, tx, cancel_requested)
     });
 
-    let mut cmd = Command::new(&python);
-    cmd.arg("-m")
-        .arg(format!("python.{}", module))
-        .args(args)
-        .env("PYTHONPATH", &python_parent)
-        .env("PYTHONIOENCODING", "utf-8")
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped());
+    while let Some(event) = rx.recv().await {
+        match event.event.as_str() {
+            "progress" => {
+                let _ = app.emit("download-progress", ProgressPayload {
+                    job_id,
+                    stage: event.stage.unwrap_or_default(),
+                    percent: event.percent.unwrap_or(0.0),
+                    speed_mbps: event.speed_mbps.unwrap_or(0.0),
+                    eta_seconds: event.eta_seconds.unwrap_or(0.0),
+                    fps: event.fps.unwrap_or(0.0),
+                });
+            }
+            "log" | "raw" => {
+                let _ = app.emit("download-log", LogPayload {
+                    job_id,
+                    level: event.level.unwrap_or_else(|| "info".to_string()),
+                    message: event.message.unwrap_or_default(),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    let (result, error_code, error_msg, stderr_output) = outcome_task.await
+        .map_err(|e| format!("Python module '{}' task panicked: {}", module, e))??;
+
+    // Log any stderr output (may contain Python tracebacks)
+    if !stderr_output.is_empty() {
+        for line in stderr_output.lines().take(20) {
+            let line = line.trim();
+            if !line.is_empty() {
+                let _ = app.emit("download-log", LogPayload {
+                    job_id,
+                    level: "debug".to_string(),
+                    message: format!("[stderr] {}", line),
+                });
+            }
+        }
+    }
+
+    if let Some(msg) = error_msg {
+        let code = error_code.unwrap_or_else(|| "unknown".to_string());
+        Err(format!("{}: {}", code, msg))
+    } else if let Some(data) = result {
+        Ok(data)
+    } else {
+        // No result event â€” include stderr in the error message for debugging
+        let stderr_summary = stderr_output.lines()
+            .filter(|l| !l.trim().is_empty())
+            .collect::<Vec<_>>()
+            .join(" | ");
+        if stderr_summary.is_empty() {
+            Err(format!("Python module '{}' returned no result (PYTHONPATH={})", module, python_parent))
+        } else {
+            Err(format!("Python error: {}", stderr_summary))
+        }
+    }
+}
+
+type ModuleOutcome = (Option<serde_json::Value>, Option<String>, Option<String>, String);
 
+/// Spawn the child and synchronously read its stdout/stderr. Runs on a
+/// blocking thread (see `run_python_module`); parsed events are forwarded
+/// over `tx` as they arrive so the async side can re-emit them live.
+fn run_child_blocking(
+    mut cmd: Command,
+    process_handle: Option<ProcessHandle>,
+    tx: tokio::sync::mpsc::UnboundedSender<PythonEvent>,
+    cancel_requested: Option<CancelFlag>,
+) -> Result<ModuleOutcome, String> {
     let mut child = cmd.spawn().map_err(|e| format!("Failed to spawn Python: {}", e))?;
 
+    // A cancel may have been requested before this job's process even
+    // existed to cancel (e.g. right after `start_download`, while the
+    // job was still queued). Check the flag the moment we have a child,
+    // regardless of the graceful/force distinction `stop_process` makes
+    // for an already-registered process.
+    if cancel_requested.as_ref().map_or(false, |f| f.load(Ordering::SeqCst)) {
+        let _ = child.kill();
+        let _ = child.wait();
+        return Err("cancelled".to_string());
+    }
+
     let stdout = child.stdout.take().ok_or("Failed to capture stdout")?;
     let stderr = child.stderr.take().ok_or("Failed to capture stderr")?;
 
@@ -156,7 +390,15 @@ pub async fn run_python_module(
 
     // Store child for cancellation if a handle was provided
     if let Some(ref handle) = process_handle {
-        let mut guard = handle.lock().await;
+        let mut guard = handle.blocking_lock();
+        // Re-check the cancel flag under the same lock that registers the
+        // child: a cancel landing after the check above but before this
+        // point would otherwise find no process for `stop_process` to stop.
+        if cancel_requested.as_ref().map_or(false, |f| f.load(Ordering::SeqCst)) {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err("cancelled".to_string());
+        }
         *guard = Some(child);
     }
 
@@ -165,8 +407,6 @@ pub async fn run_python_module(
     let mut error_msg: Option<String> = None;
     let mut error_code: Option<String> = None;
 
-    let app_clone = app.clone();
-
     for line in reader.lines() {
         let line = match line {
             Ok(l) => l,
@@ -180,84 +420,97 @@ pub async fn run_python_module(
         let event: PythonEvent = match serde_json::from_str(&line) {
             Ok(e) => e,
             Err(_) => {
-                let _ = app_clone.emit("download-log", LogPayload {
-                    level: "debug".to_string(),
-                    message: line,
+                let _ = tx.send(PythonEvent {
+                    event: "raw".to_string(),
+                    level: Some("debug".to_string()),
+                    message: Some(line),
+                    ..Default::default()
                 });
                 continue;
             }
         };
 
         match event.event.as_str() {
-            "progress" => {
-                let _ = app_clone.emit("download-progress", ProgressPayload {
-                    stage: event.stage.unwrap_or_default(),
-                    percent: event.percent.unwrap_or(0.0),
-                    speed_mbps: event.speed_mbps.unwrap_or(0.0),
-                    eta_seconds: event.eta_seconds.unwrap_or(0.0),
-                    fps: event.fps.unwrap_or(0.0),
-                });
-            }
-            "log" => {
-                let _ = app_clone.emit("download-log", LogPayload {
-                    level: event.level.unwrap_or_else(|| "info".to_string()),
-                    message: event.message.unwrap_or_default(),
-                });
-            }
-            "result" => {
-                result = event.data;
-            }
+            "result" => result = event.data.clone(),
             "error" => {
-                error_code = event.code;
-                error_msg = event.message;
+                error_code = event.code.clone();
+                error_msg = event.message.clone();
             }
             _ => {}
         }
+
+        let _ = tx.send(event);
     }
 
     // Wait for the process to finish
     if let Some(ref handle) = process_handle {
-        let mut guard = handle.lock().await;
+        let mut guard = handle.blocking_lock();
         if let Some(ref mut child) = *guard {
             let _ = child.wait();
         }
         *guard = None;
     }
 
-    // Collect stderr
     let stderr_output = stderr_thread.join().unwrap_or_default();
-    if !stderr_output.is_empty() {
-        // Log any stderr output (may contain Python tracebacks)
-        for line in stderr_output.lines().take(20) {
-            let line = line.trim();
-            if !line.is_empty() {
-                let _ = app.emit("download-log", LogPayload {
-                    level: "debug".to_string(),
-                    message: format!("[stderr] {}", line),
-                });
+
+    Ok((result, error_code, error_msg, stderr_output))
+}
+
+/// Ask a child process to shut down, preferring a gentle interrupt so
+/// yt-dlp/ffmpeg can flush partial files and leave resumable `.part` state
+/// behind. Waits up to `grace` before escalating to `kill()`, unless
+/// `force` is set, in which case it kills immediately.
+pub async fn stop_process(handle: &ProcessHandle, grace: std::time::Duration, force: bool) {
+    let pid = {
+        let guard = handle.lock().await;
+        guard.as_ref().map(|child| child.id())
+    };
+    let Some(pid) = pid else { return };
+
+    if !force {
+        send_interrupt(pid);
+
+        let deadline = tokio::time::Instant::now() + grace;
+        loop {
+            {
+                let mut guard = handle.lock().await;
+                match guard.as_mut() {
+                    Some(child) => match child.try_wait() {
+                        Ok(Some(_)) | Err(_) => {
+                            *guard = None;
+                            return;
+                        }
+                        Ok(None) => {}
+                    },
+                    None => return,
+                }
             }
+            if tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
         }
     }
 
-    if let Some(msg) = error_msg {
-        let code = error_code.unwrap_or_else(|| "unknown".to_string());
-        Err(format!("{}: {}", code, msg))
-    } else if let Some(data) = result {
-        Ok(data)
-    } else {
-        // No result event â€” include stderr in the error message for debugging
-        let stderr_summary = stderr_output.lines()
-            .filter(|l| !l.trim().is_empty())
-            .collect::<Vec<_>>()
-            .join(" | ");
-        if stderr_summary.is_empty() {
-            Err(format!("Python module '{}' returned no result (PYTHONPATH={})", module, python_parent))
-        } else {
-            Err(format!("Python error: {}", stderr_summary))
-        }
+    kill_process(handle).await;
+}
+
+#[cfg(unix)]
+fn send_interrupt(pid: u32) {
+    unsafe {
+        libc::kill(pid as libc::pid_t, libc::SIGINT);
     }
 }
 
+#[cfg(windows)]
+fn send_interrupt(pid: u32) {
+    // Windows has no remote SIGINT; a plain (non-forceful) taskkill asks
+    // the process to close instead of terminating it outright.
+    let _ = std::process::Command::new("taskkill")
+        .args(["/PID", &pid.to_string()])
+        .status();
+}
+
 /// Kill a running Python process
 pub async fn kill_process(handle: &ProcessHandle) {
     let mut guard = handle.lock().await;
@@ -0,0 +1,165 @@
+use serde::Deserialize;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+
+const REPO: &str = "yt-dlp/yt-dlp";
+const USER_AGENT: &str = "MediaPull";
+
+/// How long to wait on a single GitHub API/download request before giving
+/// up, so a stalled connection can't hang update checks/installs forever.
+const GITHUB_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+#[derive(Debug, Deserialize)]
+struct GithubAsset {
+    name: String,
+    browser_download_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GithubRelease {
+    tag_name: String,
+    assets: Vec<GithubAsset>,
+}
+
+/// Directory the managed yt-dlp binary and its version marker live in.
+fn ytdlp_dir(app: &AppHandle) -> Result<PathBuf, String> {
+    let dir = app.path().app_data_dir().map_err(|e| e.to_string())?.join("yt-dlp");
+    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+    Ok(dir)
+}
+
+fn binary_name() -> &'static str {
+    if cfg!(target_os = "windows") { "yt-dlp.exe" } else { "yt-dlp" }
+}
+
+/// The release asset name this platform should download.
+fn asset_name() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "yt-dlp.exe"
+    } else if cfg!(target_os = "macos") {
+        "yt-dlp_macos"
+    } else {
+        "yt-dlp"
+    }
+}
+
+/// Path to the managed yt-dlp binary, whether or not it has been installed yet.
+pub fn binary_path(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(ytdlp_dir(app)?.join(binary_name()))
+}
+
+fn version_file(app: &AppHandle) -> Result<PathBuf, String> {
+    Ok(ytdlp_dir(app)?.join("version.txt"))
+}
+
+/// Version recorded after the last successful install, if any.
+pub fn installed_version(app: &AppHandle) -> Option<String> {
+    std::fs::read_to_string(version_file(app).ok()?).ok().map(|s| s.trim().to_string())
+}
+
+async fn fetch_release(nightly: bool) -> Result<GithubRelease, String> {
+    let url = if nightly {
+        format!("https://api.github.com/repos/{REPO}/releases/tags/nightly")
+    } else {
+        format!("https://api.github.com/repos/{REPO}/releases/latest")
+    };
+
+    let client = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(GITHUB_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+
+    let response = client.get(&url).send().await.map_err(|e| format!("GitHub request failed: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!("GitHub returned {} for {}", response.status(), url));
+    }
+    response.json::<GithubRelease>().await.map_err(|e| format!("Failed to parse GitHub release: {}", e))
+}
+
+fn find_asset<'a>(release: &'a GithubRelease) -> Result<&'a GithubAsset, String> {
+    release.assets.iter()
+        .find(|a| a.name == asset_name())
+        .ok_or_else(|| format!("No yt-dlp release asset named '{}'", asset_name()))
+}
+
+/// Compare the installed binary's recorded version against the latest
+/// GitHub release (stable or nightly) and report whether an update is
+/// available.
+pub async fn check_for_update(app: &AppHandle, nightly: bool) -> Result<serde_json::Value, String> {
+    let release = fetch_release(nightly).await?;
+    let current = installed_version(app);
+    let update_available = current.as_deref() != Some(release.tag_name.as_str());
+
+    Ok(serde_json::json!({
+        "current_version": current,
+        "latest_version": release.tag_name,
+        "nightly": nightly,
+        "update_available": update_available,
+    }))
+}
+
+/// Smoke-test a freshly downloaded binary with `--version` before it's
+/// trusted enough to become the managed yt-dlp binary - catches a truncated
+/// download, an HTML error page served as the asset body, or a binary for
+/// the wrong platform before it ever gets invoked against a real download.
+async fn verify_binary(path: &std::path::Path) -> Result<(), String> {
+    let path = path.to_path_buf();
+    let output = tauri::async_runtime::spawn_blocking(move || {
+        std::process::Command::new(&path).arg("--version").output()
+    })
+    .await
+    .map_err(|e| format!("Verification task panicked: {}", e))?
+    .map_err(|e| format!("Failed to execute: {}", e))?;
+
+    if !output.status.success() {
+        return Err(format!("exited with {}", output.status));
+    }
+    Ok(())
+}
+
+/// Download the given release's binary for this platform, verify it runs,
+/// and atomically swap it into place as the managed yt-dlp binary.
+pub async fn install_update(app: &AppHandle, version: &str, nightly: bool) -> Result<serde_json::Value, String> {
+    let release = fetch_release(nightly).await?;
+    if release.tag_name != version {
+        return Err(format!("Requested version {} is no longer the latest ({})", version, release.tag_name));
+    }
+    let asset = find_asset(&release)?;
+
+    let client = reqwest::Client::builder()
+        .user_agent(USER_AGENT)
+        .timeout(GITHUB_TIMEOUT)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let bytes = client.get(&asset.browser_download_url)
+        .send().await.map_err(|e| format!("Download failed: {}", e))?
+        .bytes().await.map_err(|e| format!("Download failed: {}", e))?;
+
+    let dir = ytdlp_dir(app)?;
+    let final_path = dir.join(binary_name());
+    let tmp_path = dir.join(format!("{}.download", binary_name()));
+
+    std::fs::write(&tmp_path, &bytes).map_err(|e| format!("Failed to write binary: {}", e))?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&tmp_path).map_err(|e| e.to_string())?.permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&tmp_path, perms).map_err(|e| e.to_string())?;
+    }
+
+    verify_binary(&tmp_path).await.map_err(|e| {
+        let _ = std::fs::remove_file(&tmp_path);
+        format!("Downloaded yt-dlp binary failed verification: {}", e)
+    })?;
+
+    std::fs::rename(&tmp_path, &final_path).map_err(|e| format!("Failed to install binary: {}", e))?;
+    std::fs::write(version_file(app)?, &release.tag_name).map_err(|e| e.to_string())?;
+
+    Ok(serde_json::json!({
+        "installed_version": release.tag_name,
+        "path": final_path.to_string_lossy(),
+    }))
+}